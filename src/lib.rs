@@ -2,28 +2,63 @@ use pythonize::pythonize;
 
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
 
+use regex::bytes::Regex as BytesRegex;
+use regex::Regex;
 use sqlparser::parser::Parser;
 // no PyDict needed when we accept a single Authorization string
 
+mod list_ops;
 mod opteryx_dialect;
+mod sqloxide;
 mod temporal_parser;
 
-pub use opteryx_dialect::OpteryxDialect;
+pub use list_ops::{
+    allop_cmp_bool, allop_cmp_f64, allop_cmp_i64, allop_cmp_utf8, anyop_cmp_bool, anyop_cmp_f64,
+    anyop_cmp_i64, anyop_cmp_utf8, anyop_eq_numeric, anyop_eq_string,
+};
+pub use opteryx_dialect::{normalize_postgres_literals, OpteryxDialect};
+pub use sqloxide::transpile_sql;
 pub use temporal_parser::{extract_temporal_for_clauses, TemporalExtractionResult, TemporalFilter};
 
-/// Convert Python-style backreferences (\1, \2, etc.) to Rust-style ($1, $2, etc.)
+/// Convert Python-style backreferences (\1, \2, etc.) to Rust-style ($1, $2,
+/// etc.).
+///
+/// When the character right after the backreference's digits is itself
+/// alphanumeric, the digits are wrapped in `${...}` (e.g. `\1x` -> `${1}x`
+/// rather than `$1x`) so Rust's replacement syntax doesn't swallow it as part
+/// of a longer group name/number.
+///
+/// Python's `re.sub` treats `$` in the replacement as a literal character,
+/// while Rust's `replace_all` treats `$name` as a group reference, so any
+/// bare `$` that isn't one we just generated is escaped to `$$` to keep
+/// `REGEXP_REPLACE` matching Python semantics (e.g. `'$5.00'` stays
+/// `$5.00`, not an empty-group substitution).
 fn convert_python_to_rust_backrefs(replacement: &str) -> String {
     let mut result = String::new();
     let mut chars = replacement.chars().peekable();
-    
+
     while let Some(ch) = chars.next() {
         if ch == '\\' {
             if let Some(&next_ch) = chars.peek() {
                 if next_ch.is_ascii_digit() {
-                    // This is a backreference like \1
-                    result.push('$');
-                    // Don't consume the next char, just peek
+                    // This is a backreference like \1 - consume every digit.
+                    let mut digits = String::new();
+                    while let Some(&d) = chars.peek() {
+                        if d.is_ascii_digit() {
+                            digits.push(d);
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    if chars.peek().is_some_and(|c| c.is_alphanumeric()) {
+                        result.push_str(&format!("${{{digits}}}"));
+                    } else {
+                        result.push('$');
+                        result.push_str(&digits);
+                    }
                 } else {
                     // Regular escape sequence, keep the backslash
                     result.push(ch);
@@ -32,11 +67,15 @@ fn convert_python_to_rust_backrefs(replacement: &str) -> String {
                 // Backslash at end of string
                 result.push(ch);
             }
+        } else if ch == '$' {
+            // A literal `$` from the input, not one we generated above -
+            // escape it so Rust doesn't read it as a group reference.
+            result.push_str("$$");
         } else {
             result.push(ch);
         }
     }
-    
+
     result
 }
 
@@ -50,6 +89,7 @@ fn convert_python_to_rust_backrefs(replacement: &str) -> String {
 #[pyo3(text_signature = "(sql, dialect)")]
 fn parse_sql(py: Python, sql: String, _dialect: String) -> PyResult<Py<PyAny>> {
     let chosen_dialect = Box::new(OpteryxDialect {});
+    let sql = normalize_postgres_literals(&sql);
     let parse_result = Parser::parse_sql(&*chosen_dialect, &sql);
 
     let output = match parse_result {
@@ -69,11 +109,10 @@ fn parse_sql(py: Python, sql: String, _dialect: String) -> PyResult<Py<PyAny>> {
 }
 
 /// Extract temporal FOR clauses from SQL.
-/// Returns a dictionary with 'clean_sql' (SQL with FOR clauses removed) 
+/// Returns a dictionary with 'clean_sql' (SQL with FOR clauses removed)
 /// and 'filters' (list of temporal filter information).
-/// 
-/// **Note**: This is a proof-of-concept. The Python implementation in
-/// sql_rewriter.py remains the production version.
+///
+/// This replaces the Python `sql_rewriter.py` implementation.
 #[pyfunction]
 #[pyo3(text_signature = "(sql)")]
 fn extract_temporal_filters(py: Python, sql: String) -> PyResult<Py<PyAny>> {
@@ -97,25 +136,75 @@ fn extract_temporal_filters(py: Python, sql: String) -> PyResult<Py<PyAny>> {
 /// 
 /// Returns:
 /// - List of strings or bytes with replacements applied
+#[pyfunction]
 fn regex_replace_rust(
     py: Python,
     data: Vec<Option<Py<PyAny>>>,
     pattern: Py<PyAny>,
     replacement: Py<PyAny>,
 ) -> PyResult<Vec<Option<Py<PyAny>>>> {
-    // Currently a stub implementation for the regex PoC.
-    // Full implementation requires FromPyObject handling which we
-    // will reintroduce after stabilizing the IO PoC. Return an
-    // empty vector for now.
-    Ok(Vec::new())
+    let pattern = pattern.bind(py);
+    let replacement = replacement.bind(py);
+
+    if pattern.is_instance_of::<PyBytes>() {
+        let pattern_str = std::str::from_utf8(pattern.downcast::<PyBytes>()?.as_bytes())
+            .map_err(|e| PyValueError::new_err(format!("Pattern is not valid UTF-8.\n\t{e}")))?;
+        let replacement_str =
+            std::str::from_utf8(replacement.downcast::<PyBytes>()?.as_bytes()).map_err(|e| {
+                PyValueError::new_err(format!("Replacement is not valid UTF-8.\n\t{e}"))
+            })?;
+        let rust_replacement = convert_python_to_rust_backrefs(replacement_str);
+
+        let regex = BytesRegex::new(pattern_str)
+            .map_err(|e| PyValueError::new_err(format!("Invalid regex pattern.\n\t{e}")))?;
+
+        data.into_iter()
+            .map(|item| {
+                item.map(|value| -> PyResult<Py<PyAny>> {
+                    let bytes = value.bind(py).downcast::<PyBytes>()?.as_bytes();
+                    let replaced = regex.replace_all(bytes, rust_replacement.as_bytes());
+                    Ok(PyBytes::new(py, &replaced).into())
+                })
+                .transpose()
+            })
+            .collect()
+    } else {
+        let pattern_str = pattern.downcast::<PyString>()?.to_str()?;
+        let replacement_str = replacement.downcast::<PyString>()?.to_str()?;
+        let rust_replacement = convert_python_to_rust_backrefs(replacement_str);
+
+        let regex = Regex::new(pattern_str)
+            .map_err(|e| PyValueError::new_err(format!("Invalid regex pattern.\n\t{e}")))?;
+
+        data.into_iter()
+            .map(|item| {
+                item.map(|value| -> PyResult<Py<PyAny>> {
+                    let s = value.bind(py).downcast::<PyString>()?.to_str()?;
+                    let replaced = regex.replace_all(s, rust_replacement.as_str());
+                    Ok(PyString::new(py, &replaced).into())
+                })
+                .transpose()
+            })
+            .collect()
+    }
 }
 
 
 #[pymodule]
 fn compute(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(parse_sql, m)?)?;
+    m.add_function(wrap_pyfunction!(transpile_sql, m)?)?;
     m.add_function(wrap_pyfunction!(extract_temporal_filters, m)?)?;
-    // `regex_replace_rust` is currently kept internal (not exposed)
-    // to reduce PyO3 surface area during the IO PoC iteration.
+    m.add_function(wrap_pyfunction!(regex_replace_rust, m)?)?;
+    m.add_function(wrap_pyfunction!(anyop_cmp_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(anyop_cmp_f64, m)?)?;
+    m.add_function(wrap_pyfunction!(anyop_cmp_bool, m)?)?;
+    m.add_function(wrap_pyfunction!(anyop_cmp_utf8, m)?)?;
+    m.add_function(wrap_pyfunction!(allop_cmp_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(allop_cmp_f64, m)?)?;
+    m.add_function(wrap_pyfunction!(allop_cmp_bool, m)?)?;
+    m.add_function(wrap_pyfunction!(allop_cmp_utf8, m)?)?;
+    m.add_function(wrap_pyfunction!(anyop_eq_numeric, m)?)?;
+    m.add_function(wrap_pyfunction!(anyop_eq_string, m)?)?;
     Ok(())
 }
\ No newline at end of file
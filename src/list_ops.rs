@@ -1,40 +1,153 @@
-use numpy::{PyArray1, PyArray2, IntoPyArray};
-use pyo3::{Python, PyResult, prelude::*};
+use numpy::{IntoPyArray, PyArray1, PyArray2};
+use pyo3::{prelude::*, PyResult, Python};
 
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyAny, PyString};
 
-#[pyfunction]
-pub fn anyop_eq_numeric(py: Python<'_>, literal: i64, arr: &PyArray2<i64>) -> PyResult<Py<PyArray1<bool>>> {
+/// The comparison operators the `anyop`/`allop` kernels support. These back
+/// the `@>` (`AtArrow` / ANY) and `@>>` (`ArrayContainsAll` / ALL)
+/// operators `OpteryxDialect` parses, generalized beyond plain equality.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    fn parse(op: &str) -> PyResult<Self> {
+        match op {
+            "=" | "==" => Ok(CompareOp::Eq),
+            "!=" | "<>" => Ok(CompareOp::Ne),
+            "<" => Ok(CompareOp::Lt),
+            "<=" => Ok(CompareOp::Le),
+            ">" => Ok(CompareOp::Gt),
+            ">=" => Ok(CompareOp::Ge),
+            other => Err(PyValueError::new_err(format!(
+                "Unsupported comparison operator '{other}'"
+            ))),
+        }
+    }
+
+    fn apply<T: PartialOrd>(self, item: &T, literal: &T) -> bool {
+        match self {
+            CompareOp::Eq => item == literal,
+            CompareOp::Ne => item != literal,
+            CompareOp::Lt => item < literal,
+            CompareOp::Le => item <= literal,
+            CompareOp::Gt => item > literal,
+            CompareOp::Ge => item >= literal,
+        }
+    }
+}
+
+/// `ANY`: does at least one element of each row satisfy `op literal`?
+fn anyop_numeric<T: PartialOrd + Copy>(arr: &PyArray2<T>, literal: T, op: CompareOp) -> Vec<bool> {
     let array = unsafe { arr.as_array() };
-    let result = array.map_axis(ndarray::Axis(1), |row| {
-        row.iter().any(|&item| item == literal)
-    });
-    Ok(result.into_pyarray(py).to_owned())
+    array
+        .map_axis(ndarray::Axis(1), |row| {
+            row.iter().any(|item| op.apply(item, &literal))
+        })
+        .to_vec()
 }
 
+/// `ALL` (the `@>>` / `ArrayContainsAll` case): does every element of each
+/// row satisfy `op literal`?
+fn allop_numeric<T: PartialOrd + Copy>(arr: &PyArray2<T>, literal: T, op: CompareOp) -> Vec<bool> {
+    let array = unsafe { arr.as_array() };
+    array
+        .map_axis(ndarray::Axis(1), |row| {
+            row.iter().all(|item| op.apply(item, &literal))
+        })
+        .to_vec()
+}
 
-use pyo3::types::{PyAny, PyString};
+macro_rules! numeric_cmp_kernels {
+    ($ty:ty, $any_name:ident, $all_name:ident) => {
+        #[pyfunction]
+        pub fn $any_name(
+            py: Python<'_>,
+            op: &str,
+            literal: $ty,
+            arr: &PyArray2<$ty>,
+        ) -> PyResult<Py<PyArray1<bool>>> {
+            let op = CompareOp::parse(op)?;
+            Ok(anyop_numeric(arr, literal, op).into_pyarray(py).to_owned())
+        }
 
-#[pyfunction]
-pub fn anyop_eq_string(_py: Python, value: &str, arr: &PyAny) -> PyResult<Vec<bool>> {
-    // Assume `arr` is a 2D array-like object (e.g., numpy array or list of lists)
-    let rows = arr.getattr("shape")?.extract::<(usize, )>()?.0;
-    let mut results = Vec::new();
+        #[pyfunction]
+        pub fn $all_name(
+            py: Python<'_>,
+            op: &str,
+            literal: $ty,
+            arr: &PyArray2<$ty>,
+        ) -> PyResult<Py<PyArray1<bool>>> {
+            let op = CompareOp::parse(op)?;
+            Ok(allop_numeric(arr, literal, op).into_pyarray(py).to_owned())
+        }
+    };
+}
+
+numeric_cmp_kernels!(i64, anyop_cmp_i64, allop_cmp_i64);
+numeric_cmp_kernels!(f64, anyop_cmp_f64, allop_cmp_f64);
+numeric_cmp_kernels!(bool, anyop_cmp_bool, allop_cmp_bool);
+
+/// Row-wise ANY/ALL over a one-dimensional array of rows (e.g. a numpy object
+/// array whose elements are sequences of strings), comparing each element to
+/// `value` with `op`. `arr` must have a one-element `shape`, so plain Python
+/// lists and 2D arrays are rejected with an error.
+fn cmp_utf8(op: CompareOp, value: &str, arr: &PyAny, all: bool) -> PyResult<Vec<bool>> {
+    let rows = arr.getattr("shape")?.extract::<(usize,)>()?.0;
+    let mut results = Vec::with_capacity(rows);
 
     for i in 0..rows {
         let row = arr.get_item((i,))?;
-        let mut found = false;
-        
-        // Assuming `row` can be iterated over, reflecting a sequence of strings.
+        let mut matched = all;
         for item in row.iter()? {
             let item_str = item?.downcast::<PyString>()?.to_str()?;
-            if item_str == value {
-                found = true;
+            if op.apply(&item_str, &value) {
+                if !all {
+                    matched = true;
+                    break;
+                }
+            } else if all {
+                matched = false;
                 break;
             }
         }
-        
-        results.push(found);
+        results.push(matched);
     }
 
     Ok(results)
 }
+
+#[pyfunction]
+pub fn anyop_cmp_utf8(_py: Python, op: &str, value: &str, arr: &PyAny) -> PyResult<Vec<bool>> {
+    cmp_utf8(CompareOp::parse(op)?, value, arr, false)
+}
+
+#[pyfunction]
+pub fn allop_cmp_utf8(_py: Python, op: &str, value: &str, arr: &PyAny) -> PyResult<Vec<bool>> {
+    cmp_utf8(CompareOp::parse(op)?, value, arr, true)
+}
+
+/// Kept for existing callers: `ANY element == literal` over a numeric 2D array.
+#[pyfunction]
+pub fn anyop_eq_numeric(
+    py: Python<'_>,
+    literal: i64,
+    arr: &PyArray2<i64>,
+) -> PyResult<Py<PyArray1<bool>>> {
+    Ok(anyop_numeric(arr, literal, CompareOp::Eq)
+        .into_pyarray(py)
+        .to_owned())
+}
+
+/// Kept for existing callers: `ANY element == value` over a 2D array-like of strings.
+#[pyfunction]
+pub fn anyop_eq_string(_py: Python, value: &str, arr: &PyAny) -> PyResult<Vec<bool>> {
+    cmp_utf8(CompareOp::Eq, value, arr, false)
+}
@@ -136,3 +136,394 @@ impl Dialect for OpteryxDialect {
     }
 
 }
+
+/// Rewrite PostgreSQL-only string literal forms that `OpteryxDialect`'s
+/// tokenizer doesn't understand on its own - `$tag$ ... $tag$` dollar-quoted
+/// strings, `E'...'` C-style escape strings, and `U&'...'` unicode-escape
+/// strings - into plain `'...'` literals before the SQL reaches
+/// `sqlparser::Parser`. See:
+/// https://www.postgresql.org/docs/current/sql-syntax-lexical.html#SQL-SYNTAX-DOLLAR-QUOTING
+/// https://www.postgresql.org/docs/current/sql-syntax-lexical.html#SQL-SYNTAX-STRINGS-ESCAPE
+/// https://www.postgresql.org/docs/current/sql-syntax-lexical.html#SQL-SYNTAX-STRINGS-UESCAPE
+///
+/// Folding these into a regular quoted literal up front - rather than adding
+/// another `Dialect` flag - keeps `supports_string_literal_backslash_escape()
+/// == false` unchanged for every other string literal in the query.
+pub fn normalize_postgres_literals(sql: &str) -> String {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut out = String::with_capacity(sql.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+
+        // Leave existing quoted regions and comments untouched.
+        match ch {
+            '\'' | '"' | '`' => {
+                let quote = ch;
+                out.push(ch);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    let matched = chars[i] == quote;
+                    i += 1;
+                    if matched {
+                        break;
+                    }
+                }
+                continue;
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    out.push(chars[i]);
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                out.push(chars[i]);
+                out.push(chars[i + 1]);
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    out.push(chars[i]);
+                    i += 1;
+                }
+                if i < chars.len() {
+                    out.push(chars[i]);
+                    out.push(chars[i + 1]);
+                    i += 2;
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        // `$tag$ ... $tag$`
+        if ch == '$' {
+            if let Some((tag_len, body_start)) = dollar_quote_tag(&chars, i) {
+                let tag: String = chars[i + 1..i + 1 + tag_len].iter().collect();
+                let delim: Vec<char> = format!("${tag}$").chars().collect();
+                if let Some(close) = find_subsequence(&chars, body_start, &delim) {
+                    let body: String = chars[body_start..close].iter().collect();
+                    out.push('\'');
+                    out.push_str(&body.replace('\'', "''"));
+                    out.push('\'');
+                    i = close + delim.len();
+                    continue;
+                }
+            }
+        }
+
+        // `E'...'` / `e'...'`
+        if (ch == 'E' || ch == 'e') && chars.get(i + 1) == Some(&'\'') {
+            if let Some((body, end)) = scan_e_quoted(&chars, i + 1) {
+                out.push('\'');
+                out.push_str(&unescape_c_style(&body).replace('\'', "''"));
+                out.push('\'');
+                i = end;
+                continue;
+            }
+        }
+
+        // `U&'...'` / `u&'...'`
+        if (ch == 'U' || ch == 'u')
+            && chars.get(i + 1) == Some(&'&')
+            && chars.get(i + 2) == Some(&'\'')
+        {
+            if let Some((body, end)) = scan_quoted(&chars, i + 2) {
+                out.push('\'');
+                out.push_str(&unescape_unicode(&body).replace('\'', "''"));
+                out.push('\'');
+                i = end;
+                continue;
+            }
+        }
+
+        out.push(ch);
+        i += 1;
+    }
+
+    out
+}
+
+/// Returns `(tag_len, body_start_index)` if `chars[at]` begins a dollar-quote
+/// tag (`$`, an optional identifier, then `$`).
+fn dollar_quote_tag(chars: &[char], at: usize) -> Option<(usize, usize)> {
+    let mut j = at + 1;
+    while j < chars.len() && (chars[j].is_alphanumeric() || chars[j] == '_') {
+        j += 1;
+    }
+    if chars.get(j) == Some(&'$') {
+        Some((j - at - 1, j + 1))
+    } else {
+        None
+    }
+}
+
+fn find_subsequence(chars: &[char], from: usize, needle: &[char]) -> Option<usize> {
+    if needle.is_empty() || from + needle.len() > chars.len() {
+        return None;
+    }
+    (from..=chars.len() - needle.len()).find(|&start| chars[start..start + needle.len()] == *needle)
+}
+
+/// Scans a `'...'` literal starting at the opening quote (`chars[at] == '\''`),
+/// honouring `''` as an escaped quote. Returns the literal body (with `''`
+/// collapsed to `'`) and the index just past the closing quote.
+fn scan_quoted(chars: &[char], at: usize) -> Option<(String, usize)> {
+    let mut body = String::new();
+    let mut j = at + 1;
+    while j < chars.len() {
+        if chars[j] == '\'' {
+            if chars.get(j + 1) == Some(&'\'') {
+                body.push('\'');
+                j += 2;
+                continue;
+            }
+            return Some((body, j + 1));
+        }
+        body.push(chars[j]);
+        j += 1;
+    }
+    None
+}
+
+/// Scans an `E'...'` literal body starting at the opening quote, honouring
+/// both `''` and the C-style `\'` as an escaped quote (and `\\` so an
+/// escaped backslash never eats the quote that follows it). Unlike
+/// `scan_quoted`, a lone `\'` does not close the literal. The returned body
+/// still has its backslash escapes in raw form - `unescape_c_style` resolves
+/// those afterwards.
+fn scan_e_quoted(chars: &[char], at: usize) -> Option<(String, usize)> {
+    let mut body = String::new();
+    let mut j = at + 1;
+    while j < chars.len() {
+        match chars[j] {
+            '\\' if j + 1 < chars.len() => {
+                body.push(chars[j]);
+                body.push(chars[j + 1]);
+                j += 2;
+            }
+            '\'' if chars.get(j + 1) == Some(&'\'') => {
+                body.push('\'');
+                j += 2;
+            }
+            '\'' => return Some((body, j + 1)),
+            c => {
+                body.push(c);
+                j += 1;
+            }
+        }
+    }
+    None
+}
+
+/// Resolves PostgreSQL `E'...'` C-style escapes: `\n`, `\t`, `\\`, `\'`, ...,
+/// plus `\xHH` hex, `\uXXXX`/`\UXXXXXXXX` unicode, and `\ooo` octal escapes.
+/// See https://www.postgresql.org/docs/current/sql-syntax-lexical.html#SQL-SYNTAX-STRINGS-ESCAPE
+fn unescape_c_style(body: &str) -> String {
+    let chars: Vec<char> = body.chars().collect();
+    let mut out = String::with_capacity(body.len());
+    let mut i = 0;
+
+    // Consumes up to `max_digits` characters matching `is_digit` and returns
+    // them as a string; its length is the number of characters consumed.
+    fn take_digits(chars: &[char], from: usize, max_digits: usize, is_digit: fn(char) -> bool) -> String {
+        let mut digits = String::new();
+        let mut j = from;
+        while j < chars.len() && digits.len() < max_digits && is_digit(chars[j]) {
+            digits.push(chars[j]);
+            j += 1;
+        }
+        digits
+    }
+
+    while i < chars.len() {
+        if chars[i] != '\\' {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        let Some(&escape) = chars.get(i + 1) else {
+            out.push('\\');
+            break;
+        };
+        match escape {
+            'n' => {
+                out.push('\n');
+                i += 2;
+            }
+            't' => {
+                out.push('\t');
+                i += 2;
+            }
+            'r' => {
+                out.push('\r');
+                i += 2;
+            }
+            'b' => {
+                out.push('\u{0008}');
+                i += 2;
+            }
+            'f' => {
+                out.push('\u{000C}');
+                i += 2;
+            }
+            '\\' => {
+                out.push('\\');
+                i += 2;
+            }
+            '\'' => {
+                out.push('\'');
+                i += 2;
+            }
+            'x' => {
+                let hex = take_digits(&chars, i + 2, 2, |c| c.is_ascii_hexdigit());
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(c) if !hex.is_empty() => {
+                        out.push(c);
+                        i += 2 + hex.len();
+                    }
+                    _ => {
+                        out.push('x');
+                        i += 2;
+                    }
+                }
+            }
+            'u' => {
+                let hex = take_digits(&chars, i + 2, 4, |c| c.is_ascii_hexdigit());
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(c) if hex.len() == 4 => {
+                        out.push(c);
+                        i += 2 + hex.len();
+                    }
+                    _ => {
+                        out.push('u');
+                        i += 2;
+                    }
+                }
+            }
+            'U' => {
+                let hex = take_digits(&chars, i + 2, 8, |c| c.is_ascii_hexdigit());
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(c) if hex.len() == 8 => {
+                        out.push(c);
+                        i += 2 + hex.len();
+                    }
+                    _ => {
+                        out.push('U');
+                        i += 2;
+                    }
+                }
+            }
+            '0'..='7' => {
+                let oct = take_digits(&chars, i + 1, 3, |c| ('0'..='7').contains(&c));
+                if let Some(c) = u32::from_str_radix(&oct, 8).ok().and_then(char::from_u32) {
+                    out.push(c);
+                }
+                i += 1 + oct.len();
+            }
+            other => {
+                out.push('\\');
+                out.push(other);
+                i += 2;
+            }
+        }
+    }
+    out
+}
+
+/// Resolves PostgreSQL `U&'...'` unicode escapes: `\XXXX` and `\+XXXXXX`.
+fn unescape_unicode(body: &str) -> String {
+    let chars: Vec<char> = body.chars().collect();
+    let mut out = String::with_capacity(body.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '\\' {
+            if chars.get(i + 1) == Some(&'+') && i + 8 <= chars.len() {
+                let hex: String = chars[i + 2..i + 8].iter().collect();
+                if let Ok(code) = u32::from_str_radix(&hex, 16) {
+                    if let Some(c) = char::from_u32(code) {
+                        out.push(c);
+                        i += 8;
+                        continue;
+                    }
+                }
+            } else if i + 5 <= chars.len() {
+                let hex: String = chars[i + 1..i + 5].iter().collect();
+                if let Ok(code) = u32::from_str_radix(&hex, 16) {
+                    if let Some(c) = char::from_u32(code) {
+                        out.push(c);
+                        i += 5;
+                        continue;
+                    }
+                }
+            }
+        }
+        out.push(chars[i]);
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlparser::parser::Parser;
+
+    #[test]
+    fn test_dollar_prefixed_identifier_still_parses_as_a_relation() {
+        // `$planets` etc. are Opteryx's system datasets - `is_identifier_start`
+        // admits `$` for exactly this reason. Dollar-quoted *string literals*
+        // are handled separately by `normalize_postgres_literals` before the
+        // parser ever sees them, so this must keep working without any
+        // `supports_dollar_placeholder` help from the `Dialect` impl.
+        let statements = Parser::parse_sql(&OpteryxDialect {}, "SELECT * FROM $planets")
+            .expect("`$planets` should parse as a relation");
+        assert_eq!(statements.len(), 1);
+        assert!(statements[0].to_string().contains("$planets"));
+    }
+
+    #[test]
+    fn test_dollar_quoted_string() {
+        let sql = "SELECT $$it's a test$$ FROM planets";
+        assert_eq!(normalize_postgres_literals(sql), "SELECT 'it''s a test' FROM planets");
+    }
+
+    #[test]
+    fn test_dollar_quoted_string_with_tag() {
+        let sql = "SELECT $tag$hello $$ world$tag$";
+        assert_eq!(normalize_postgres_literals(sql), "SELECT 'hello $$ world'");
+    }
+
+    #[test]
+    fn test_escape_string() {
+        let sql = r"SELECT E'line1\nline2'";
+        assert_eq!(normalize_postgres_literals(sql), "SELECT 'line1\nline2'");
+    }
+
+    #[test]
+    fn test_escape_string_with_escaped_quote() {
+        let sql = r"SELECT E'a\'b'";
+        assert_eq!(normalize_postgres_literals(sql), "SELECT 'a''b'");
+    }
+
+    #[test]
+    fn test_escape_string_hex_and_octal() {
+        let sql = r"SELECT E'\x41\101'";
+        assert_eq!(normalize_postgres_literals(sql), "SELECT 'AA'");
+    }
+
+    #[test]
+    fn test_unicode_escape_string() {
+        let sql = r"SELECT U&'\0041\0042'";
+        assert_eq!(normalize_postgres_literals(sql), "SELECT 'AB'");
+    }
+
+    #[test]
+    fn test_leaves_plain_strings_untouched() {
+        let sql = "SELECT 'hello world' FROM planets";
+        assert_eq!(normalize_postgres_literals(sql), sql);
+    }
+}
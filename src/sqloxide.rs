@@ -4,11 +4,15 @@ use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
 use pythonize::PythonizeError;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
 
 use sqlparser::ast::Statement;
 use sqlparser::dialect::*;
 use sqlparser::parser::Parser;
 
+use crate::OpteryxDialect;
+
 fn string_to_dialect(dialect: &str) -> Box<dyn Dialect> {
     match dialect.to_lowercase().as_str() {
         "ansi" => Box::new(AnsiDialect {}),
@@ -18,6 +22,7 @@ fn string_to_dialect(dialect: &str) -> Box<dyn Dialect> {
         "hive" => Box::new(HiveDialect {}),
         "ms" | "mssql" => Box::new(MsSqlDialect {}),
         "mysql" => Box::new(MySqlDialect {}),
+        "opteryx" => Box::new(OpteryxDialect {}),
         "postgres" => Box::new(PostgreSqlDialect {}),
         "redshift" => Box::new(RedshiftSqlDialect {}),
         "snowflake" => Box::new(SnowflakeDialect {}),
@@ -91,3 +96,202 @@ pub fn restore_ast(_py: Python, ast: &PyAny) -> PyResult<Vec<String>> {
         .collect::<Vec<String>>())
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct TranspileResult {
+    pub statements: Vec<String>,
+    pub unsupported: Vec<String>,
+}
+
+/// Rewrite every `quote_style` equal to the source dialect's
+/// delimited-identifier character (`from_quote`) to the one the target
+/// dialect uses (`to_quote`). Other quote styles are left unchanged, and
+/// nothing is rewritten when either dialect reports no quote character.
+///
+/// `Statement` doesn't expose a visitor we can hook into here, so this walks the
+/// serde representation of the AST instead - the same representation `parse_sql`
+/// already hands to Python via `pythonize`.
+fn rewrite_quote_styles(value: &mut JsonValue, from_quote: Option<char>, to_quote: Option<char>) {
+    match value {
+        JsonValue::Object(map) => {
+            if let (Some(from_ch), Some(to_ch)) = (from_quote, to_quote) {
+                if let Some(JsonValue::String(s)) = map.get("quote_style") {
+                    if s.chars().next() == Some(from_ch) {
+                        map.insert("quote_style".to_string(), JsonValue::String(to_ch.to_string()));
+                    }
+                }
+            }
+            for v in map.values_mut() {
+                rewrite_quote_styles(v, from_quote, to_quote);
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items.iter_mut() {
+                rewrite_quote_styles(item, from_quote, to_quote);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns true if `value`'s AST contains an identifier whose `quote_style`
+/// is `quote` - i.e. whether rewriting *to* a dialect with no delimited
+/// identifiers at all (`identifier_quote_style` returning `None`) would
+/// actually drop information.
+fn has_quote_style(value: &JsonValue, quote: char) -> bool {
+    match value {
+        JsonValue::Object(map) => {
+            if let Some(JsonValue::String(s)) = map.get("quote_style") {
+                if s.chars().next() == Some(quote) {
+                    return true;
+                }
+            }
+            map.values().any(|v| has_quote_style(v, quote))
+        }
+        JsonValue::Array(items) => items.iter().any(|v| has_quote_style(v, quote)),
+        _ => false,
+    }
+}
+
+/// Returns true if `dialect` understands the OpteryxDialect-specific operators
+/// (`DIV`, `@>`, `@>>`) that `OpteryxDialect::parse_infix` introduces.
+fn supports_opteryx_operator(dialect: &str, op: &str) -> bool {
+    match op {
+        "DIV" => matches!(dialect.to_lowercase().as_str(), "opteryx" | "mysql"),
+        "@>" | "@>>" => dialect.to_lowercase() == "opteryx",
+        _ => true,
+    }
+}
+
+/// Walk the serde representation of the AST looking for constructs that the
+/// target dialect has no equivalent for, recording a human-readable note for
+/// each one found rather than silently dropping them.
+fn collect_unsupported(value: &JsonValue, to_dialect: &str, out: &mut Vec<String>) {
+    match value {
+        // Unit `BinaryOperator` variants (`MyIntegerDivide`, `AtArrow`) serde
+        // to plain JSON strings, not object keys - only the `Custom(String)`
+        // newtype variant below becomes an object.
+        JsonValue::String(s) => {
+            if s == "MyIntegerDivide" && !supports_opteryx_operator(to_dialect, "DIV") {
+                out.push(format!(
+                    "`DIV` (integer division) has no equivalent in the '{to_dialect}' dialect; left as-is"
+                ));
+            }
+            if s == "AtArrow" && !supports_opteryx_operator(to_dialect, "@>") {
+                out.push(format!(
+                    "`@>` (array-contains) has no equivalent in the '{to_dialect}' dialect; left as-is"
+                ));
+            }
+        }
+        JsonValue::Object(map) => {
+            if let Some(JsonValue::String(custom)) = map.get("Custom") {
+                if custom == "ArrayContainsAll" && !supports_opteryx_operator(to_dialect, "@>>") {
+                    out.push(format!(
+                        "`@>>` (array-contains-all) has no equivalent in the '{to_dialect}' dialect; left as-is"
+                    ));
+                }
+            }
+            for v in map.values() {
+                collect_unsupported(v, to_dialect, out);
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items {
+                collect_unsupported(item, to_dialect, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Parse `sql` with `from_dialect` and re-render it using `to_dialect`'s
+/// formatting rules, so e.g. Postgres/Snowflake/BigQuery SQL can be normalized
+/// into the `OpteryxDialect` form Opteryx executes.
+///
+/// sqlparser-rs's `Display` impl for `Statement` is dialect-agnostic, so the
+/// lexical differences we care about - delimited-identifier quoting, and the
+/// handful of operators `OpteryxDialect` introduces (`DIV`, `@>`, `@>>`) - are
+/// handled explicitly here rather than by a general AST rewrite. Constructs
+/// that can't be faithfully represented in the target dialect are reported in
+/// `unsupported` instead of being silently dropped.
+#[pyfunction]
+#[pyo3(text_signature = "(sql, from_dialect, to_dialect)")]
+pub fn transpile_sql(
+    py: Python,
+    sql: &str,
+    from_dialect: &str,
+    to_dialect: &str,
+) -> PyResult<PyObject> {
+    let source_dialect = string_to_dialect(from_dialect);
+    let target_dialect = string_to_dialect(to_dialect);
+
+    let statements = Parser::parse_sql(&*source_dialect, sql).map_err(|e| {
+        let msg = e.to_string();
+        PyValueError::new_err(format!("Query parsing failed.\n\t{msg}"))
+    })?;
+
+    let mut value = serde_json::to_value(&statements).map_err(|e| {
+        PyValueError::new_err(format!("AST serialization failed.\n\t{e}"))
+    })?;
+
+    let mut unsupported = Vec::new();
+    collect_unsupported(&value, to_dialect, &mut unsupported);
+
+    let from_quote = source_dialect.identifier_quote_style("");
+    let to_quote = target_dialect.identifier_quote_style("");
+    if let (Some(from_ch), None) = (from_quote, to_quote) {
+        if has_quote_style(&value, from_ch) {
+            unsupported.push(format!(
+                "the '{to_dialect}' dialect has no delimited-identifier quoting; identifiers quoted with `{from_ch}` in '{from_dialect}' are left as-is"
+            ));
+        }
+    }
+    rewrite_quote_styles(&mut value, from_quote, to_quote);
+
+    let retargeted: Vec<Statement> = serde_json::from_value(value).map_err(|e| {
+        PyValueError::new_err(format!("AST deserialization failed.\n\t{e}"))
+    })?;
+
+    let result = TranspileResult {
+        statements: retargeted.iter().map(ToString::to_string).collect(),
+        unsupported,
+    };
+
+    pythonize(py, &result)
+        .map(Into::into)
+        .map_err(|e| {
+            let msg = e.to_string();
+            PyValueError::new_err(format!("Python object serialization failed.\n\t{msg}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_has_quote_style_finds_matching_identifier() {
+        let value = json!({
+            "relation": {
+                "name": [{"value": "planets", "quote_style": "`"}]
+            }
+        });
+        assert!(has_quote_style(&value, '`'));
+        assert!(!has_quote_style(&value, '"'));
+    }
+
+    #[test]
+    fn test_has_quote_style_false_when_absent() {
+        let value = json!({"relation": {"name": [{"value": "planets", "quote_style": null}]}});
+        assert!(!has_quote_style(&value, '`'));
+    }
+
+    #[test]
+    fn test_rewrite_quote_styles_no_op_when_target_has_none() {
+        let mut value = json!({"name": [{"value": "planets", "quote_style": "`"}]});
+        let original = value.clone();
+        rewrite_quote_styles(&mut value, Some('`'), None);
+        assert_eq!(value, original);
+    }
+}
+
@@ -4,40 +4,21 @@
 // Distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND.
 
 //! Temporal FOR Clause Parser
-//! 
-//! This module provides a proof-of-concept for Rust-based parsing of Opteryx's 
-//! temporal FOR clauses. This demonstrates how temporal extraction could potentially 
-//! be moved from Python to Rust in the future.
-//! 
-//! ## Current Status
-//! 
-//! **THIS IS A PROOF OF CONCEPT FOR INVESTIGATION PURPOSES**
-//! 
-//! The Python implementation in sql_rewriter.py remains the authoritative version
-//! and should continue to be used in production. This Rust version demonstrates
-//! feasibility and provides a foundation if native Rust implementation is pursued later.
-//! 
+//!
+//! This module provides a Rust implementation of Opteryx's temporal `FOR`
+//! clause extraction, replacing the Python `sql_rewriter.py` implementation.
+//!
 //! ## FOR Clause Syntax
-//! 
+//!
 //! Opteryx supports temporal filtering with FOR clauses:
 //! - `FOR <timestamp>` - single point in time
+//! - `FOR TODAY` / `FOR YESTERDAY` - named single day
 //! - `FOR DATES BETWEEN <start> AND <end>` - date range
 //! - `FOR DATES IN <range>` - named range (THIS_MONTH, LAST_MONTH)
 //! - `FOR DATES SINCE <timestamp>` - from timestamp to now
 //! - `FOR LAST <n> DAYS` - last n days
-//! 
+//!
 //! Example: `SELECT * FROM planets FOR TODAY`
-//! 
-//! ## Implementation Notes
-//! 
-//! The Python implementation uses a sophisticated state machine that handles:
-//! - Quoted strings (with b"" and r"" prefixes for binary and raw strings)
-//! - SQL comments
-//! - Special functions that use FROM keyword (EXTRACT, SUBSTRING, TRIM)
-//! - Nested subqueries
-//! - Multiple table references with different temporal filters
-//! 
-//! A complete Rust port requires handling all these cases correctly.
 
 use serde::{Deserialize, Serialize};
 
@@ -53,34 +34,354 @@ pub struct TemporalExtractionResult {
     pub filters: Vec<TemporalFilter>,
 }
 
-/// Extract FOR clauses from SQL and return cleaned SQL plus temporal filters
-/// 
-/// **NOTE**: This is a proof-of-concept implementation for investigation.
-/// Use the Python version in sql_rewriter.py for production.
-/// 
-/// # Example (Internal Crate Usage)
-/// 
-/// ```
-/// # use crate::temporal_parser::extract_temporal_for_clauses;
-/// let result = extract_temporal_for_clauses("SELECT * FROM planets");
-/// assert_eq!(result.filters.len(), 0);
-/// ```
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Word,
+    Punct,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+    start: usize,
+    end: usize,
+}
+
+impl Token {
+    fn is_word(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Word && self.text.eq_ignore_ascii_case(keyword)
+    }
+
+    fn is_punct(&self, ch: char) -> bool {
+        self.kind == TokenKind::Punct && self.text.len() == 1 && self.text.starts_with(ch)
+    }
+}
+
+const FUNCTIONS_USING_FROM: &[&str] = &["EXTRACT", "SUBSTRING", "TRIM"];
+
+/// Keywords that close an identifier/alias chain - if we see one of these
+/// right after a relation, it is not a bare alias.
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "WHERE", "GROUP", "HAVING", "ORDER", "LIMIT", "JOIN", "INNER", "LEFT", "RIGHT", "FULL",
+    "OUTER", "CROSS", "ON", "UNION", "FOR", "AND", "OR",
+];
+
+/// Keywords that end a `FOR ...` temporal clause.
+const CLAUSE_BOUNDARIES: &[&str] = &["WHERE", "GROUP", "HAVING", "ORDER", "LIMIT", "JOIN", "ON"];
+
+/// Splits `sql` into significant tokens (words and punctuation), preserving
+/// the byte span of each token in the original string. Quoted regions -
+/// `'...'`, `"..."`, `` `...` ``, and the `b"..."`/`r"..."` prefixed forms -
+/// and `--`/`/* */` comments are skipped over as opaque spans so keywords
+/// inside them are never matched.
+fn tokenize(sql: &str) -> Vec<Token> {
+    // Indexed by char position (not byte position) so the scan never splits
+    // a multi-byte codepoint - `OpteryxDialect::is_identifier_start` admits
+    // Unicode identifiers (`\u{0080}..=\u{ffff}`), e.g. `café`.
+    let chars: Vec<(usize, char)> = sql.char_indices().collect();
+    let len = chars.len();
+    let byte_len = sql.len();
+    let byte_at = |idx: usize| -> usize {
+        if idx < len {
+            chars[idx].0
+        } else {
+            byte_len
+        }
+    };
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < len {
+        let ch = chars[i].1;
+
+        // String/byte/raw literals: '...' "..." `...` b"..." r'...' etc.
+        if ch == '\'' || ch == '"' || ch == '`' {
+            i = skip_quoted(&chars, i, ch);
+            continue;
+        }
+        if (ch == 'b' || ch == 'B' || ch == 'r' || ch == 'R')
+            && i + 1 < len
+            && matches!(chars[i + 1].1, '\'' | '"')
+        {
+            let quote = chars[i + 1].1;
+            i = skip_quoted(&chars, i + 1, quote);
+            continue;
+        }
+
+        // Comments.
+        if ch == '-' && i + 1 < len && chars[i + 1].1 == '-' {
+            while i < len && chars[i].1 != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if ch == '/' && i + 1 < len && chars[i + 1].1 == '*' {
+            i += 2;
+            while i + 1 < len && !(chars[i].1 == '*' && chars[i + 1].1 == '/') {
+                i += 1;
+            }
+            i = (i + 2).min(len);
+            continue;
+        }
+
+        // Whitespace.
+        if ch.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // Words: identifiers, keywords, numbers.
+        if ch.is_alphanumeric() || ch == '_' || ch == '$' || ch == '@' {
+            let start = i;
+            while i < len {
+                let c = chars[i].1;
+                if c.is_alphanumeric() || c == '_' || c == '$' || c == '@' {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token {
+                kind: TokenKind::Word,
+                text: sql[byte_at(start)..byte_at(i)].to_string(),
+                start: byte_at(start),
+                end: byte_at(i),
+            });
+            continue;
+        }
+
+        // Significant punctuation; everything else (operators, etc.) is
+        // skipped as it never participates in relation/FOR-clause matching.
+        if matches!(ch, '(' | ')' | ',' | '.') {
+            tokens.push(Token {
+                kind: TokenKind::Punct,
+                text: ch.to_string(),
+                start: byte_at(i),
+                end: byte_at(i + 1),
+            });
+        }
+        i += 1;
+    }
+
+    tokens
+}
+
+/// Skips a quoted region starting at `start` (the opening quote, as a char
+/// index into `chars`), honouring `''`/`""`/` `` ` as an escaped quote.
+/// Returns the char index just past the closing quote.
+fn skip_quoted(chars: &[(usize, char)], start: usize, quote: char) -> usize {
+    let len = chars.len();
+    let mut i = start + 1;
+    while i < len {
+        let ch = chars[i].1;
+        if ch == quote {
+            if i + 1 < len && chars[i + 1].1 == quote {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    len
+}
+
+/// Consumes an identifier chain (`schema.table` or just `table`) plus an
+/// optional alias (`[AS] alias`) starting at token index `start`. Returns the
+/// index of the first token after the relation and the span of source text
+/// the relation (with alias) covers.
+fn consume_relation(tokens: &[Token], start: usize) -> Option<(usize, (usize, usize))> {
+    if start >= tokens.len() || tokens[start].kind != TokenKind::Word {
+        return None;
+    }
+    let rel_start = tokens[start].start;
+    let mut i = start + 1;
+    let mut rel_end = tokens[start].end;
+
+    while i + 1 < tokens.len() && tokens[i].is_punct('.') && tokens[i + 1].kind == TokenKind::Word
+    {
+        rel_end = tokens[i + 1].end;
+        i += 2;
+    }
+
+    // Optional `AS alias` / bare `alias`.
+    if i < tokens.len() && tokens[i].is_word("AS") {
+        if i + 1 < tokens.len() && tokens[i + 1].kind == TokenKind::Word {
+            rel_end = tokens[i + 1].end;
+            i += 2;
+        }
+    } else if i < tokens.len()
+        && tokens[i].kind == TokenKind::Word
+        && !CLAUSE_KEYWORDS.iter().any(|kw| tokens[i].is_word(kw))
+    {
+        rel_end = tokens[i].end;
+        i += 1;
+    }
+
+    Some((i, (rel_start, rel_end)))
+}
+
+/// Consumes a `FOR ...` temporal clause. `for_token_end` is the byte offset
+/// just past the `FOR` keyword itself, and `start` is the index of the first
+/// token after it. Stops at a clause boundary keyword, a comma, a closing
+/// paren, or end-of-input. Returns the index of the first token after the
+/// clause and the span of source text the clause body (excluding `FOR`)
+/// covers.
+///
+/// The clause start is anchored to `for_token_end` rather than `tokens[start]`
+/// because a bare `FOR <timestamp>` clause - e.g. `FOR '2024-01-01'` - is a
+/// single quoted literal that emits no token at all; anchoring to the next
+/// token would skip straight past it.
+fn consume_for_clause(
+    tokens: &[Token],
+    for_token_end: usize,
+    start: usize,
+    sql_len: usize,
+) -> (usize, (usize, usize)) {
+    let clause_start = for_token_end;
+    let mut i = start;
+
+    while i < tokens.len() {
+        let tok = &tokens[i];
+        if tok.is_punct(')') || tok.is_punct(',') {
+            break;
+        }
+        if tok.kind == TokenKind::Word && CLAUSE_BOUNDARIES.iter().any(|kw| tok.is_word(kw)) {
+            break;
+        }
+        i += 1;
+    }
+
+    // Use the boundary token's start (rather than the end of the last
+    // matched token) so any untokenized content just before it - e.g. a
+    // quoted timestamp literal - stays part of the clause. With no boundary
+    // token at all, the clause runs to the end of the SQL string.
+    let clause_end = if i < tokens.len() {
+        tokens[i].start
+    } else {
+        sql_len
+    };
+
+    (i, (clause_start, clause_end))
+}
+
+/// Extract FOR clauses from SQL and return cleaned SQL plus temporal filters.
 pub fn extract_temporal_for_clauses(sql: &str) -> TemporalExtractionResult {
-    // TODO: Implement full temporal extraction logic
-    // For now, this is a placeholder that returns SQL unchanged
-    // 
-    // The full implementation needs to:
-    // 1. Split SQL into parts while preserving quoted strings
-    // 2. Run the state machine to identify relations and FOR clauses
-    // 3. Extract temporal information
-    // 4. Reconstruct SQL without FOR clauses
-    // 
-    // See opteryx/planner/sql_rewriter.py for the reference implementation
-    
-    TemporalExtractionResult {
-        clean_sql: sql.to_string(),
-        filters: Vec::new(),
+    let tokens = tokenize(sql);
+    let mut filters = Vec::new();
+    let mut removed_spans: Vec<(usize, usize)> = Vec::new();
+
+    // Tracks, per paren depth, whether that `(` opened a call to one of
+    // `FUNCTIONS_USING_FROM` - if so, a `FROM` at that depth is an argument,
+    // not a relation introducer.
+    let mut function_paren_depth: Vec<bool> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let tok = &tokens[i];
+
+        if tok.is_punct('(') {
+            let is_function_call = i > 0
+                && tokens[i - 1].kind == TokenKind::Word
+                && FUNCTIONS_USING_FROM
+                    .iter()
+                    .any(|f| tokens[i - 1].is_word(f));
+            function_paren_depth.push(is_function_call);
+            i += 1;
+            continue;
+        }
+        if tok.is_punct(')') {
+            function_paren_depth.pop();
+            i += 1;
+            continue;
+        }
+
+        let is_relation_keyword = tok.is_word("FROM") || tok.is_word("JOIN");
+        if is_relation_keyword {
+            let suppressed = tok.is_word("FROM") && function_paren_depth.last() == Some(&true);
+            if suppressed {
+                i += 1;
+                continue;
+            }
+
+            // A `FROM`/`JOIN` can introduce more than one relation via
+            // comma-separated old-style joins (`FROM a FOR TODAY, b FOR
+            // YESTERDAY`) - keep consuming `relation [FOR ...]` pairs across
+            // commas until one doesn't parse as a relation.
+            let mut next = i + 1;
+            let mut consumed_any = false;
+            while let Some((after_relation, relation_span)) = consume_relation(&tokens, next) {
+                consumed_any = true;
+                let mut after = after_relation;
+                if after < tokens.len() && tokens[after].is_word("FOR") {
+                    let for_token_end = tokens[after].end;
+                    let (after_clause, clause_span) =
+                        consume_for_clause(&tokens, for_token_end, after + 1, sql.len());
+                    let relation = sql[relation_span.0..relation_span.1].to_string();
+                    let clause_text = &sql[clause_span.0..clause_span.1];
+                    let temporal_clause = clause_text.trim().to_string();
+                    filters.push(TemporalFilter {
+                        relation,
+                        temporal_clause,
+                    });
+                    // Remove the `FOR ...` span, including the `FOR` keyword
+                    // itself, from the cleaned SQL. The whitespace after the
+                    // clause is left for `remove_spans` to collapse.
+                    let cut_end = clause_span.0 + clause_text.trim_end().len();
+                    removed_spans.push((tokens[after].start, cut_end));
+                    after = after_clause;
+                }
+                next = after;
+                if next < tokens.len() && tokens[next].is_punct(',') {
+                    next += 1;
+                    continue;
+                }
+                break;
+            }
+            if consumed_any {
+                i = next;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    let clean_sql = remove_spans(sql, &removed_spans);
+
+    TemporalExtractionResult { clean_sql, filters }
+}
+
+/// Removes the given byte spans from `sql`. Only the whitespace immediately
+/// adjacent to each cut is collapsed (down to a single separating space, or
+/// none at all at start/end-of-string) - text elsewhere in the string,
+/// including the insides of string literals, is left byte-for-byte
+/// untouched. `split_whitespace`-style global collapsing would also flatten
+/// multi-space runs and newlines inside literals far from any removed span.
+fn remove_spans(sql: &str, spans: &[(usize, usize)]) -> String {
+    if spans.is_empty() {
+        return sql.to_string();
+    }
+
+    let mut out = String::with_capacity(sql.len());
+    let mut cursor = 0;
+    for &(start, end) in spans {
+        out.push_str(&sql[cursor..start]);
+        cursor = end;
+
+        let trimmed_len = out.trim_end_matches(char::is_whitespace).len();
+        out.truncate(trimmed_len);
+
+        let rest = &sql[cursor..];
+        let leading_ws = rest.len() - rest.trim_start_matches(char::is_whitespace).len();
+        if leading_ws > 0 && leading_ws < rest.len() {
+            out.push(' ');
+        }
+        cursor += leading_ws;
     }
+    out.push_str(&sql[cursor..]);
+    out
 }
 
 #[cfg(test)]
@@ -94,6 +395,162 @@ mod tests {
         assert_eq!(result.filters.len(), 0);
         assert!(result.clean_sql.contains("planets"));
     }
-    
-    // Additional tests would go here as the implementation progresses
+
+    #[test]
+    fn test_for_today() {
+        let result = extract_temporal_for_clauses("SELECT * FROM planets FOR TODAY");
+        assert_eq!(result.clean_sql, "SELECT * FROM planets");
+        assert_eq!(
+            result.filters,
+            vec![TemporalFilter {
+                relation: "planets".to_string(),
+                temporal_clause: "TODAY".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_for_dates_between() {
+        let result = extract_temporal_for_clauses(
+            "SELECT * FROM planets FOR DATES BETWEEN '2024-01-01' AND '2024-01-31' WHERE id > 1",
+        );
+        assert_eq!(result.clean_sql, "SELECT * FROM planets WHERE id > 1");
+        assert_eq!(result.filters[0].relation, "planets");
+        assert_eq!(
+            result.filters[0].temporal_clause,
+            "DATES BETWEEN '2024-01-01' AND '2024-01-31'"
+        );
+    }
+
+    #[test]
+    fn test_schema_qualified_relation_with_alias() {
+        let result =
+            extract_temporal_for_clauses("SELECT * FROM solar.planets AS p FOR LAST 7 DAYS");
+        assert_eq!(result.filters[0].relation, "solar.planets AS p");
+        assert_eq!(result.filters[0].temporal_clause, "LAST 7 DAYS");
+    }
+
+    #[test]
+    fn test_bare_alias() {
+        let result = extract_temporal_for_clauses("SELECT * FROM planets p FOR YESTERDAY");
+        assert_eq!(result.filters[0].relation, "planets p");
+        assert_eq!(result.clean_sql, "SELECT * FROM planets p");
+    }
+
+    #[test]
+    fn test_join_with_temporal_clauses() {
+        let sql = "SELECT * FROM planets FOR TODAY JOIN moons FOR DATES SINCE '2024-01-01' ON planets.id = moons.planet_id";
+        let result = extract_temporal_for_clauses(sql);
+        assert_eq!(
+            result.clean_sql,
+            "SELECT * FROM planets JOIN moons ON planets.id = moons.planet_id"
+        );
+        assert_eq!(result.filters.len(), 2);
+        assert_eq!(result.filters[0].relation, "planets");
+        assert_eq!(result.filters[0].temporal_clause, "TODAY");
+        assert_eq!(result.filters[1].relation, "moons");
+        assert_eq!(result.filters[1].temporal_clause, "DATES SINCE '2024-01-01'");
+    }
+
+    #[test]
+    fn test_extract_function_from_is_not_a_relation() {
+        let sql = "SELECT EXTRACT(YEAR FROM birth_date) FROM people FOR TODAY";
+        let result = extract_temporal_for_clauses(sql);
+        assert_eq!(result.filters.len(), 1);
+        assert_eq!(result.filters[0].relation, "people");
+        assert_eq!(
+            result.clean_sql,
+            "SELECT EXTRACT(YEAR FROM birth_date) FROM people"
+        );
+    }
+
+    #[test]
+    fn test_nested_subquery_each_depth_tracks_its_own_relation() {
+        let sql = "SELECT * FROM planets FOR TODAY WHERE id IN (SELECT planet_id FROM moons FOR YESTERDAY)";
+        let result = extract_temporal_for_clauses(sql);
+        assert_eq!(result.filters.len(), 2);
+        assert_eq!(result.filters[0].relation, "planets");
+        assert_eq!(result.filters[0].temporal_clause, "TODAY");
+        assert_eq!(result.filters[1].relation, "moons");
+        assert_eq!(result.filters[1].temporal_clause, "YESTERDAY");
+        assert_eq!(
+            result.clean_sql,
+            "SELECT * FROM planets WHERE id IN (SELECT planet_id FROM moons)"
+        );
+    }
+
+    #[test]
+    fn test_for_bare_timestamp_literal() {
+        let result =
+            extract_temporal_for_clauses("SELECT * FROM planets FOR '2022-01-01' WHERE x");
+        assert_eq!(result.filters[0].relation, "planets");
+        assert_eq!(result.filters[0].temporal_clause, "'2022-01-01'");
+        assert_eq!(result.clean_sql, "SELECT * FROM planets WHERE x");
+    }
+
+    #[test]
+    fn test_for_bare_timestamp_literal_at_end_of_input() {
+        let result = extract_temporal_for_clauses("SELECT * FROM planets FOR '2022-01-01'");
+        assert_eq!(result.filters[0].relation, "planets");
+        assert_eq!(result.filters[0].temporal_clause, "'2022-01-01'");
+        assert_eq!(result.clean_sql, "SELECT * FROM planets");
+    }
+
+    #[test]
+    fn test_unicode_identifier_does_not_panic() {
+        let result = extract_temporal_for_clauses("SELECT * FROM café FOR TODAY");
+        assert_eq!(result.filters[0].relation, "café");
+        assert_eq!(result.clean_sql, "SELECT * FROM café");
+    }
+
+    #[test]
+    fn test_preserves_internal_whitespace_in_unrelated_string_literals() {
+        let sql = "SELECT * FROM planets FOR TODAY WHERE name = 'a   b'";
+        let result = extract_temporal_for_clauses(sql);
+        assert_eq!(
+            result.clean_sql,
+            "SELECT * FROM planets WHERE name = 'a   b'"
+        );
+    }
+
+    #[test]
+    fn test_preserves_newlines_in_unrelated_string_literals() {
+        let sql = "SELECT * FROM planets FOR TODAY WHERE name = 'line1\nline2'";
+        let result = extract_temporal_for_clauses(sql);
+        assert_eq!(
+            result.clean_sql,
+            "SELECT * FROM planets WHERE name = 'line1\nline2'"
+        );
+    }
+
+    #[test]
+    fn test_comma_separated_relations_each_get_their_own_clause() {
+        let result = extract_temporal_for_clauses(
+            "SELECT * FROM planets FOR TODAY, moons FOR YESTERDAY WHERE id > 1",
+        );
+        assert_eq!(result.filters.len(), 2);
+        assert_eq!(result.filters[0].relation, "planets");
+        assert_eq!(result.filters[0].temporal_clause, "TODAY");
+        assert_eq!(result.filters[1].relation, "moons");
+        assert_eq!(result.filters[1].temporal_clause, "YESTERDAY");
+        assert_eq!(
+            result.clean_sql,
+            "SELECT * FROM planets, moons WHERE id > 1"
+        );
+    }
+
+    #[test]
+    fn test_comma_separated_relations_without_temporal_clauses() {
+        let result = extract_temporal_for_clauses("SELECT * FROM planets, moons WHERE id > 1");
+        assert_eq!(result.filters.len(), 0);
+        assert_eq!(result.clean_sql, "SELECT * FROM planets, moons WHERE id > 1");
+    }
+
+    #[test]
+    fn test_ignores_for_keyword_inside_string_literal() {
+        let sql = "SELECT * FROM planets WHERE name = 'FOR the win'";
+        let result = extract_temporal_for_clauses(sql);
+        assert_eq!(result.filters.len(), 0);
+        assert_eq!(result.clean_sql, sql);
+    }
 }